@@ -0,0 +1,141 @@
+//! Batched append path.
+//!
+//! The example writers issue one `append_to_stream` round-trip per event, which
+//! is fine for a handful of test events but poor for bulk seeding. [`BatchAppender`]
+//! accepts `(stream, EventData)` items over a bounded channel and coalesces them
+//! into per-stream batched appends, flushing when a batch fills or a timer
+//! fires. Each submitter gets back the `WriteResult` (or error) for the batch
+//! that carried its event. The bounded channel provides backpressure so a slow
+//! server can't drive unbounded buffering.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use kurrentdb::{Client, EventData, WriteResult};
+use tokio::sync::{mpsc, oneshot};
+
+// `kurrentdb::Error` wraps non-`Clone` transport / serde errors, so a batch's
+// outcome is fanned out to every submitter as a cloneable stringified error.
+type AppendResult = Result<WriteResult, String>;
+
+/// Tuning knobs for the background batcher.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    /// Flush once this many events are buffered across all streams.
+    pub max_batch_size: usize,
+    /// Flush any buffered events at least this often.
+    pub flush_interval: Duration,
+    /// In-flight channel capacity; `append` awaits once this many items are
+    /// queued and not yet taken by the batcher (backpressure). Items already
+    /// taken into the batcher's buffer are not counted.
+    pub capacity: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 500,
+            flush_interval: Duration::from_millis(50),
+            capacity: 1_000,
+        }
+    }
+}
+
+struct Job {
+    stream: String,
+    event: EventData,
+    reply: oneshot::Sender<AppendResult>,
+}
+
+/// A handle over the client that coalesces appends behind a background task.
+#[derive(Clone)]
+pub struct BatchAppender {
+    tx: mpsc::Sender<Job>,
+}
+
+impl BatchAppender {
+    /// Spawn the background batcher and return a handle to submit events.
+    pub fn new(client: Client, config: BatchConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.capacity);
+        tokio::spawn(run(client, config, rx));
+        Self { tx }
+    }
+
+    /// Queue `event` for `stream` and await the `WriteResult` of the batch that
+    /// carries it. Awaiting applies backpressure when the buffer is full.
+    ///
+    /// Note: the returned `WriteResult` is the *batch's* aggregate result (the
+    /// revision of the last event in the flushed batch for that stream), not a
+    /// per-event result — every event coalesced into one stream's batch gets
+    /// the same `WriteResult`.
+    pub async fn append(
+        &self,
+        stream: impl Into<String>,
+        event: EventData,
+    ) -> Result<WriteResult, Box<dyn std::error::Error>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(Job {
+                stream: stream.into(),
+                event,
+                reply,
+            })
+            .await
+            .map_err(|_| "batch appender has shut down")?;
+        Ok(rx.await.map_err(|_| "batch was dropped before flushing")??)
+    }
+}
+
+async fn run(client: Client, config: BatchConfig, mut rx: mpsc::Receiver<Job>) {
+    // Events buffered per stream, paired with the reply channel to notify once
+    // the batch has been written.
+    let mut buffered: HashMap<String, Vec<(EventData, oneshot::Sender<AppendResult>)>> =
+        HashMap::new();
+    let mut pending = 0usize;
+    let mut ticker = tokio::time::interval(config.flush_interval);
+
+    loop {
+        tokio::select! {
+            maybe_job = rx.recv() => match maybe_job {
+                Some(job) => {
+                    buffered.entry(job.stream).or_default().push((job.event, job.reply));
+                    pending += 1;
+                    if pending >= config.max_batch_size {
+                        flush(&client, &mut buffered).await;
+                        pending = 0;
+                    }
+                }
+                // All handles dropped: flush the tail and exit.
+                None => {
+                    flush(&client, &mut buffered).await;
+                    break;
+                }
+            },
+            _ = ticker.tick() => {
+                if pending > 0 {
+                    flush(&client, &mut buffered).await;
+                    pending = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Write one batched append per stream and notify every waiting submitter.
+async fn flush(
+    client: &Client,
+    buffered: &mut HashMap<String, Vec<(EventData, oneshot::Sender<AppendResult>)>>,
+) {
+    for (stream, entries) in buffered.drain() {
+        let (events, replies): (Vec<EventData>, Vec<_>) = entries.into_iter().unzip();
+
+        let result = client
+            .append_to_stream(stream, &Default::default(), events)
+            .await
+            .map_err(|e| e.to_string());
+
+        for reply in replies {
+            let _ = reply.send(result.clone());
+        }
+    }
+}
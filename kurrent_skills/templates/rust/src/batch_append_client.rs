@@ -0,0 +1,162 @@
+//! High-throughput batch-append client.
+//!
+//! `main` appends one `EventData` per `append_to_stream` call, round-tripping
+//! per event. Modeled on KurrentDB's batch-append channel — a single client
+//! over which many append requests are multiplexed — [`BatchAppendClient`]
+//! accepts `(stream_name, expected_revision, Vec<EventData>)` requests and runs
+//! them with a bounded number in flight, returning a `WriteResult` per batch.
+//! The outcomes come back together, in completion order, once every batch has
+//! finished; they are not handed out one by one as each batch completes.
+//!
+//! This is the explicit-batch API (caller groups events per stream and picks an
+//! expected revision). It complements [`crate::batch_append::BatchAppender`],
+//! which instead auto-coalesces a stream of single `(stream, event)` items
+//! behind a timer. Different ergonomics for different call sites, so both are
+//! kept.
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use kurrentdb::{
+    AppendToStreamOptions, Client, EventData, ExpectedRevision, WriteResult,
+};
+
+/// One unit of work: the events to append to a stream at an expected revision.
+pub struct BatchRequest {
+    pub stream_name: String,
+    pub expected_revision: ExpectedRevision,
+    pub events: Vec<EventData>,
+}
+
+impl BatchRequest {
+    pub fn new(stream_name: impl Into<String>, events: Vec<EventData>) -> Self {
+        Self {
+            stream_name: stream_name.into(),
+            expected_revision: ExpectedRevision::Any,
+            events,
+        }
+    }
+}
+
+/// Tuning for the batch-append client.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchAppendOptions {
+    /// Per-batch deadline; a batch that exceeds it fails with a timeout.
+    pub deadline: Option<Duration>,
+    /// Maximum number of batches in flight at once (backpressure).
+    pub max_in_flight: usize,
+}
+
+impl Default for BatchAppendOptions {
+    fn default() -> Self {
+        Self {
+            deadline: Some(Duration::from_secs(30)),
+            max_in_flight: 64,
+        }
+    }
+}
+
+/// Outcome of a single batch, tagged with the stream it targeted.
+pub struct BatchOutcome {
+    pub stream_name: String,
+    pub result: Result<WriteResult, Box<dyn std::error::Error + Send + Sync>>,
+}
+
+/// A long-lived client that multiplexes many batched appends.
+#[derive(Clone)]
+pub struct BatchAppendClient {
+    client: Client,
+    options: BatchAppendOptions,
+}
+
+impl BatchAppendClient {
+    pub fn new(client: Client, options: BatchAppendOptions) -> Self {
+        Self { client, options }
+    }
+
+    /// Append every batch, running at most `max_in_flight` concurrently, and
+    /// collect the per-batch outcomes (order not guaranteed).
+    pub async fn append_batches(
+        &self,
+        batches: impl IntoIterator<Item = BatchRequest>,
+    ) -> Vec<BatchOutcome> {
+        let deadline = self.options.deadline;
+
+        futures::stream::iter(batches)
+            .map(|batch| {
+                let client = self.client.clone();
+                async move {
+                    let options =
+                        AppendToStreamOptions::default().expected_revision(batch.expected_revision);
+                    let append = client.append_to_stream(
+                        batch.stream_name.clone(),
+                        &options,
+                        batch.events,
+                    );
+
+                    let result = match deadline {
+                        Some(d) => match tokio::time::timeout(d, append).await {
+                            Ok(r) => r.map_err(|e| Box::new(e) as _),
+                            Err(_) => Err("batch append deadline exceeded".into()),
+                        },
+                        None => append.await.map_err(|e| Box::new(e) as _),
+                    };
+
+                    BatchOutcome {
+                        stream_name: batch.stream_name,
+                        result,
+                    }
+                }
+            })
+            .buffer_unordered(self.options.max_in_flight)
+            .collect()
+            .await
+    }
+}
+
+/// Bulk-seed many `OrderCreated` events across many streams with a single
+/// client, reporting throughput. Demonstrates the batch path against the
+/// per-event loop in `main`.
+pub async fn run_batch_demo() -> Result<(), Box<dyn std::error::Error>> {
+    let connection_string = std::env::var("KURRENTDB_CONNECTION_STRING")
+        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
+    let client = Client::new(connection_string.parse()?)?;
+
+    const STREAMS: usize = 100;
+    const PER_STREAM: usize = 50;
+
+    let mut batches = Vec::with_capacity(STREAMS);
+    for stream in 0..STREAMS {
+        let events = (0..PER_STREAM)
+            .map(|_| {
+                let order = crate::OrderCreated {
+                    order_id: uuid::Uuid::new_v4().to_string(),
+                    customer_id: "bulk".to_string(),
+                    amount: 1.0,
+                };
+                EventData::json("OrderCreated", &order)
+                    .map(|e| e.id(uuid::Uuid::new_v4()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        batches.push(BatchRequest::new(format!("order-bulk-{stream}"), events));
+    }
+
+    let batch_client = BatchAppendClient::new(client, BatchAppendOptions::default());
+
+    let started = Instant::now();
+    let outcomes = batch_client.append_batches(batches).await;
+    let elapsed = started.elapsed();
+
+    let failures = outcomes.iter().filter(|o| o.result.is_err()).count();
+    let total_events = STREAMS * PER_STREAM;
+    println!(
+        "Appended {} events across {} streams in {:.2?} ({:.0} events/s), {} failed batch(es)",
+        total_events,
+        STREAMS,
+        elapsed,
+        total_events as f64 / elapsed.as_secs_f64(),
+        failures
+    );
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! In-process fan-out broker.
+//!
+//! A single producer publishes a value and every live subscriber receives a
+//! clone of it. Subscribers register an unbounded sender in a slab; when a
+//! subscriber drops its receiver the corresponding sender is pruned on the next
+//! publish. This is the same shape as async-graphql's `SimpleBroker`, letting
+//! several downstream tasks react to one source without each opening its own
+//! upstream connection.
+
+use slab::Slab;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// A cheaply-cloneable handle to a fan-out channel of `T`.
+pub struct Broker<T> {
+    senders: Arc<Mutex<Slab<UnboundedSender<T>>>>,
+}
+
+impl<T> Clone for Broker<T> {
+    fn clone(&self) -> Self {
+        Self {
+            senders: Arc::clone(&self.senders),
+        }
+    }
+}
+
+impl<T> Default for Broker<T> {
+    fn default() -> Self {
+        Self {
+            senders: Arc::new(Mutex::new(Slab::new())),
+        }
+    }
+}
+
+impl<T: Clone> Broker<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new subscriber and return a stream of every value published
+    /// from now on. Dropping the returned stream prunes the subscriber.
+    pub fn subscribe(&self) -> UnboundedReceiverStream<T> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.senders.lock().unwrap().insert(tx);
+        UnboundedReceiverStream::new(rx)
+    }
+
+    /// Fan `value` out to every live subscriber, dropping any whose receiver has
+    /// gone away.
+    pub fn publish(&self, value: T) {
+        let mut senders = self.senders.lock().unwrap();
+        senders.retain(|_, tx| tx.send(value.clone()).is_ok());
+    }
+}
@@ -0,0 +1,205 @@
+//! Checkpoint stores and a reconnecting subscription driver.
+//!
+//! The resilient subscription helpers in [`crate::resilience`] reconnect on
+//! error but forget their position when the process dies. This module adds a
+//! pluggable [`CheckpointStore`] (in-memory and file-backed implementations)
+//! and a driver that persists the last processed position/revision and, on
+//! restart or reconnect, resumes from it instead of replaying from the start.
+//! The commit cadence is configurable so users trade durability against write
+//! amplification.
+//!
+//! Resuming from a stored position/revision is exclusive — the server replays
+//! from *after* the checkpoint — so this driver does no client-side dedup. That
+//! is deliberately distinct from [`crate::projection::CheckpointStore`], which
+//! persists a single projection's `$all` position to a KurrentDB stream and
+//! keeps a defensive idempotency guard for the at-least-once case. This store
+//! is the general, subscription-agnostic variant (many named subscriptions, a
+//! choice of in-memory or file backing) used by the resilient driver here.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use kurrentdb::{Client, Position, ResolvedEvent, StreamPosition, SubscriptionFilter};
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::resilience::{resilient_subscribe_to_all, resilient_subscribe_to_stream, Backoff, Retry};
+
+/// A persisted subscription position: a `$all` position or a stream revision.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Checkpoint {
+    All { commit: u64, prepare: u64 },
+    Stream(u64),
+}
+
+/// Durable storage for per-subscription checkpoints, keyed by a subscription
+/// name so one store can back many drivers.
+#[allow(async_fn_in_trait)]
+pub trait CheckpointStore {
+    async fn load(&self, name: &str) -> Result<Option<Checkpoint>, Box<dyn std::error::Error>>;
+    async fn commit(&self, name: &str, checkpoint: Checkpoint) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// A non-durable store, handy for tests and processes that tolerate replaying
+/// from the start after a crash.
+#[derive(Clone, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Arc<Mutex<HashMap<String, Checkpoint>>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, name: &str) -> Result<Option<Checkpoint>, Box<dyn std::error::Error>> {
+        Ok(self.checkpoints.lock().unwrap().get(name).copied())
+    }
+
+    async fn commit(&self, name: &str, checkpoint: Checkpoint) -> Result<(), Box<dyn std::error::Error>> {
+        self.checkpoints.lock().unwrap().insert(name.to_string(), checkpoint);
+        Ok(())
+    }
+}
+
+/// A store that persists all subscriptions' checkpoints to a single JSON file.
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    async fn read_all(&self) -> Result<HashMap<String, Checkpoint>, Box<dyn std::error::Error>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, name: &str) -> Result<Option<Checkpoint>, Box<dyn std::error::Error>> {
+        Ok(self.read_all().await?.get(name).copied())
+    }
+
+    async fn commit(&self, name: &str, checkpoint: Checkpoint) -> Result<(), Box<dyn std::error::Error>> {
+        let mut all = self.read_all().await?;
+        all.insert(name.to_string(), checkpoint);
+        tokio::fs::write(&self.path, serde_json::to_vec(&all)?).await?;
+        Ok(())
+    }
+}
+
+/// How often the driver commits a checkpoint.
+#[derive(Debug, Clone, Copy)]
+pub enum CheckpointCadence {
+    /// Commit after every `n` processed events.
+    EveryN(u32),
+    /// Commit at most once per interval.
+    Every(Duration),
+}
+
+impl CheckpointCadence {
+    fn due(&self, since: u32, last: Instant) -> bool {
+        match self {
+            CheckpointCadence::EveryN(n) => since >= *n,
+            CheckpointCadence::Every(interval) => last.elapsed() >= *interval,
+        }
+    }
+}
+
+/// Drive a resilient `$all` subscription through `handler`, resuming from and
+/// committing to `store`. Runs until the retry budget is exhausted.
+pub async fn drive_all<S, F>(
+    client: Client,
+    name: &str,
+    store: &S,
+    filter: Option<SubscriptionFilter>,
+    cadence: CheckpointCadence,
+    retry: Retry,
+    backoff: Backoff,
+    mut handler: F,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: CheckpointStore,
+    F: FnMut(&ResolvedEvent),
+{
+    let start = match store.load(name).await? {
+        Some(Checkpoint::All { commit, prepare }) => {
+            StreamPosition::Position(Position { commit, prepare })
+        }
+        _ => StreamPosition::Start,
+    };
+
+    let stream = resilient_subscribe_to_all(client, filter, start, retry, backoff);
+    tokio::pin!(stream);
+
+    let mut since = 0u32;
+    let mut last = Instant::now();
+
+    while let Some(event) = stream.next().await {
+        handler(&event);
+        since += 1;
+
+        if cadence.due(since, last) {
+            let position = event.get_original_event().position;
+            store
+                .commit(name, Checkpoint::All { commit: position.commit, prepare: position.prepare })
+                .await?;
+            since = 0;
+            last = Instant::now();
+        }
+    }
+
+    Ok(())
+}
+
+/// Drive a resilient single-stream subscription, resuming from and committing a
+/// revision checkpoint.
+pub async fn drive_stream<S, F>(
+    client: Client,
+    name: &str,
+    stream_name: String,
+    store: &S,
+    cadence: CheckpointCadence,
+    retry: Retry,
+    backoff: Backoff,
+    mut handler: F,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: CheckpointStore,
+    F: FnMut(&ResolvedEvent),
+{
+    let start = match store.load(name).await? {
+        Some(Checkpoint::Stream(revision)) => StreamPosition::Position(revision),
+        _ => StreamPosition::Start,
+    };
+
+    let stream = resilient_subscribe_to_stream(client, stream_name, start, retry, backoff);
+    tokio::pin!(stream);
+
+    let mut since = 0u32;
+    let mut last = Instant::now();
+
+    while let Some(event) = stream.next().await {
+        handler(&event);
+        since += 1;
+
+        if cadence.due(since, last) {
+            let revision = event.get_original_event().revision;
+            store.commit(name, Checkpoint::Stream(revision)).await?;
+            since = 0;
+            last = Instant::now();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Single-upstream `$all` fan-out broker.
+//!
+//! Each filtered view in `main` (`exclude_system_events`, `order-` prefix,
+//! `Order` event-type prefix) opens its own `subscribe_to_all` connection,
+//! multiplying server-side subscription load. This broker holds one upstream
+//! `subscribe_to_all` and fans every `ResolvedEvent` out over a broadcast
+//! channel to any number of in-process subscribers, each applying its own
+//! predicate client-side. Slow consumers don't block the reader: the channel
+//! is bounded, and when a consumer lags the number of events it dropped is
+//! logged to stderr.
+//!
+//! This fans out raw upstream `ResolvedEvent`s from one server subscription.
+//! It is distinct from [`crate::broker::Broker`], which fans out a projection's
+//! already-computed state updates to local consumers; different inputs, so both
+//! are kept.
+
+use std::sync::{Arc, Once};
+
+use async_stream::stream;
+use kurrentdb::{Client, ResolvedEvent, SubscribeToAllOptions};
+use tokio::sync::broadcast;
+use tokio_stream::Stream;
+
+/// Client-side predicate over `$all` events. An empty filter matches every
+/// non-dropped event.
+#[derive(Clone, Default)]
+pub struct Filter {
+    stream_prefix: Option<String>,
+    event_type_prefix: Option<String>,
+    exclude_system: bool,
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stream_prefix(mut self, prefix: &str) -> Self {
+        self.stream_prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn event_type_prefix(mut self, prefix: &str) -> Self {
+        self.event_type_prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn exclude_system_events(mut self) -> Self {
+        self.exclude_system = true;
+        self
+    }
+
+    fn matches(&self, event: &ResolvedEvent) -> bool {
+        let original = event.get_original_event();
+        if self.exclude_system && original.stream_id().starts_with('$') {
+            return false;
+        }
+        if let Some(prefix) = &self.stream_prefix {
+            if !original.stream_id().starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(prefix) = &self.event_type_prefix {
+            if !original.event_type.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+struct Inner {
+    tx: broadcast::Sender<Arc<ResolvedEvent>>,
+    client: Client,
+    started: Once,
+}
+
+/// A handle to the shared `$all` subscription.
+#[derive(Clone)]
+pub struct EventBroker {
+    inner: Arc<Inner>,
+}
+
+impl EventBroker {
+    /// Prepare a broker over a single upstream `$all` subscription. `buffer`
+    /// bounds the per-subscriber backlog. The upstream read does not start until
+    /// the first [`subscribe`](Self::subscribe), so no events are dropped before
+    /// a receiver is attached.
+    pub fn new(client: Client, buffer: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(buffer);
+        Self {
+            inner: Arc::new(Inner {
+                tx,
+                client,
+                started: Once::new(),
+            }),
+        }
+    }
+
+    /// Subscribe with a client-side `filter`. The returned stream yields only
+    /// matching events; if this consumer falls `buffer` events behind, the
+    /// lagged count is logged and delivery resumes from the oldest retained
+    /// event.
+    ///
+    /// The first call lazily spawns the upstream reader, so every event read
+    /// from `$all` is observable by at least the first subscriber (later
+    /// subscribers see events from their attach point on, as broadcast
+    /// semantics dictate).
+    pub fn subscribe(&self, filter: Filter) -> impl Stream<Item = ResolvedEvent> {
+        // Register this receiver *before* the reader can start, so the first
+        // subscriber never misses an event.
+        let mut rx = self.inner.tx.subscribe();
+
+        self.inner.started.call_once(|| {
+            let client = self.inner.client.clone();
+            let reader_tx = self.inner.tx.clone();
+            tokio::spawn(async move {
+                let mut subscription =
+                    client.subscribe_to_all(&SubscribeToAllOptions::default()).await;
+                loop {
+                    match subscription.next().await {
+                        Ok(event) => {
+                            // Errors here only mean all subscribers have gone away.
+                            let _ = reader_tx.send(Arc::new(event));
+                        }
+                        Err(e) => {
+                            eprintln!("upstream $all subscription ended: {e}");
+                            break;
+                        }
+                    }
+                }
+            });
+        });
+
+        stream! {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if filter.matches(&event) {
+                            yield (*event).clone();
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        eprintln!("consumer lagged, dropped {n} event(s)");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
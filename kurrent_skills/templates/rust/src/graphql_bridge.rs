@@ -0,0 +1,126 @@
+//! Bridge KurrentDB subscriptions to GraphQL/WebSocket live feeds.
+//!
+//! Exposes a GraphQL `events` subscription backed by `subscribe_to_all`, so web
+//! clients receive each `ResolvedEvent` pushed over a WebSocket. The subscription
+//! arguments translate into a server-side `SubscriptionFilter`, and each event
+//! is mapped into a payload carrying `stream_id`, `event_type`, `revision`, and
+//! the deserialized JSON body. When the WebSocket client disconnects,
+//! async-graphql drops the returned stream, which drops the underlying
+//! subscription task — no manual teardown needed.
+//!
+//! This bridge exposes the *raw event feed* (`stream_id`, `event_type`,
+//! `revision`, body) straight from `$all`. It is distinct from
+//! [`crate::graphql_gateway`], which serves the *projected read-model* state;
+//! the two answer different questions (event log vs. materialized view), so
+//! both GraphQL layers are kept.
+
+use async_graphql::{EmptyMutation, Json, Object, Schema, SimpleObject, Subscription};
+use async_stream::stream;
+use axum::{routing::get, Router};
+use kurrentdb::{Client, SubscribeToAllOptions, SubscriptionFilter};
+use serde_json::Value;
+use tokio_stream::Stream;
+
+/// GraphQL view of a single `$all` event.
+#[derive(SimpleObject)]
+pub struct EventPayload {
+    pub stream_id: String,
+    pub event_type: String,
+    pub revision: u64,
+    /// Deserialized event body; `null` if the payload wasn't JSON.
+    pub body: Json<Value>,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Trivial field so the schema has a query root.
+    async fn health(&self) -> &'static str {
+        "ok"
+    }
+}
+
+pub struct SubscriptionRoot {
+    client: Client,
+}
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Live feed of `$all` events, optionally filtered server-side.
+    async fn events(
+        &self,
+        stream_prefix: Option<String>,
+        event_type_prefix: Option<String>,
+        exclude_system: Option<bool>,
+    ) -> impl Stream<Item = EventPayload> {
+        let client = self.client.clone();
+
+        // Translate the GraphQL arguments into a SubscriptionFilter. A stream
+        // prefix and an event-type filter are mutually exclusive on the server,
+        // so a stream prefix takes precedence when both are supplied.
+        let mut options = SubscribeToAllOptions::default();
+        if let Some(prefix) = stream_prefix {
+            options = options.filter(SubscriptionFilter::on_stream_name().add_prefix(&prefix));
+        } else {
+            let mut filter = SubscriptionFilter::on_event_type();
+            if let Some(prefix) = event_type_prefix {
+                filter = filter.add_prefix(&prefix);
+            }
+            if exclude_system.unwrap_or(false) {
+                filter = filter.exclude_system_events();
+            }
+            options = options.filter(filter);
+        }
+
+        stream! {
+            let mut subscription = client.subscribe_to_all(&options).await;
+            loop {
+                match subscription.next().await {
+                    Ok(event) => {
+                        let original = event.get_original_event();
+                        yield EventPayload {
+                            stream_id: original.stream_id().to_string(),
+                            event_type: original.event_type.clone(),
+                            revision: original.revision,
+                            body: Json(original.as_json::<Value>().unwrap_or(Value::Null)),
+                        };
+                    }
+                    Err(e) => {
+                        eprintln!("bridge subscription ended: {e}");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub type BridgeSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+fn build_schema(client: Client) -> BridgeSchema {
+    Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot { client }).finish()
+}
+
+/// Start a small server exposing the bridge over HTTP (queries) and WebSocket
+/// (subscriptions) at `/`. Point a browser GraphQL client at it to watch live
+/// order events.
+pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
+    let connection_string = std::env::var("KURRENTDB_CONNECTION_STRING")
+        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
+    let client = Client::new(connection_string.parse()?)?;
+
+    let schema = build_schema(client);
+
+    let app = Router::new()
+        .route(
+            "/",
+            get(async_graphql_axum::GraphQLSubscription::new(schema.clone()))
+                .post_service(async_graphql_axum::GraphQL::new(schema)),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+    println!("GraphQL bridge listening on http://127.0.0.1:8000");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! GraphQL gateway over the in-memory [`Projection`].
+//!
+//! Mounts the projected read-model behind async-graphql so external clients can
+//! run `subscription { orderSummary(streamId: "...") { status amount items } }`
+//! and receive a push whenever that order's state changes, plus a one-shot
+//! `orderSummary` query backed by [`Projection::get`]. It is built on top of the
+//! fan-out broker and stays purely additive to `Projection`.
+
+use std::sync::Arc;
+
+use async_graphql::{EmptyMutation, Object, Schema, SimpleObject, Subscription};
+use axum::{routing::get, Router};
+use kurrentdb::{Client, SubscribeToAllOptions, SubscriptionFilter};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::projection::{order_summary, Projection};
+
+/// GraphQL view of a projected order's state.
+#[derive(SimpleObject, Clone)]
+pub struct OrderSummary {
+    pub status: Option<String>,
+    pub amount: Option<f64>,
+    pub items: Vec<String>,
+}
+
+impl From<&Value> for OrderSummary {
+    fn from(value: &Value) -> Self {
+        Self {
+            status: value.get("status").and_then(Value::as_str).map(str::to_string),
+            amount: value.get("amount").and_then(Value::as_f64),
+            items: value
+                .get("items")
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+type SharedProjection = Arc<RwLock<Projection>>;
+
+pub struct QueryRoot {
+    projection: SharedProjection,
+}
+
+#[Object]
+impl QueryRoot {
+    /// One-shot read of a single order's current projected state.
+    async fn order_summary(&self, stream_id: String) -> Option<OrderSummary> {
+        let projection = self.projection.read().await;
+        projection.get(&stream_id).map(OrderSummary::from)
+    }
+}
+
+pub struct SubscriptionRoot {
+    projection: SharedProjection,
+}
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Push the projected state of `stream_id` every time it changes.
+    async fn order_summary(
+        &self,
+        stream_id: String,
+    ) -> impl Stream<Item = OrderSummary> {
+        let updates = self.projection.read().await.subscribe();
+        updates.filter_map(move |(updated_id, state)| {
+            (updated_id == stream_id).then(|| OrderSummary::from(&state))
+        })
+    }
+}
+
+/// The gateway's schema type.
+pub type OrderSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+/// Build a schema that serves reads and live updates for `projection`.
+pub fn build_schema(projection: SharedProjection) -> OrderSchema {
+    Schema::build(
+        QueryRoot {
+            projection: projection.clone(),
+        },
+        EmptyMutation,
+        SubscriptionRoot { projection },
+    )
+    .finish()
+}
+
+/// Serve the read-model API end to end: run the order-summary projection
+/// against `$all` in the background and expose it over HTTP (queries) and
+/// WebSocket (subscriptions) at `/`. Point a browser GraphQL client at it to
+/// query `orderSummary` or subscribe to live changes.
+pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
+    let connection_string = std::env::var("KURRENTDB_CONNECTION_STRING")
+        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
+    let client = Client::new(connection_string.parse()?)?;
+
+    let projection: SharedProjection = Arc::new(RwLock::new(order_summary()));
+
+    // Background task: drive the projection so subscribers actually receive
+    // pushes and `orderSummary` queries return live state.
+    let driver = projection.clone();
+    let driver_client = client.clone();
+    tokio::spawn(async move {
+        let filter = SubscriptionFilter::on_event_type().exclude_system_events();
+        let options = SubscribeToAllOptions::default().filter(filter);
+        let mut subscription = driver_client.subscribe_to_all(&options).await;
+        loop {
+            match subscription.next().await {
+                Ok(event) => {
+                    let original = event.get_original_event();
+                    let stream_id = original.stream_id().to_string();
+                    let event_type = original.event_type.clone();
+                    let position = original.position;
+                    driver
+                        .write()
+                        .await
+                        .apply(&stream_id, &event_type, &original.data, position);
+                }
+                Err(e) => {
+                    eprintln!("projection driver subscription ended: {e}");
+                    break;
+                }
+            }
+        }
+    });
+
+    let schema = build_schema(projection);
+
+    let app = Router::new().route(
+        "/",
+        get(async_graphql_axum::GraphQLSubscription::new(schema.clone()))
+            .post_service(async_graphql_axum::GraphQL::new(schema)),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8001").await?;
+    println!("GraphQL read-model API listening on http://127.0.0.1:8001");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
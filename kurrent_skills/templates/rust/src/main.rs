@@ -1,21 +1,32 @@
 //! KurrentDB Rust Client Example - Append, Read, Subscribe, and Filtered Subscriptions
 
 use kurrentdb::{
-    Client, EventData, ReadStreamOptions, StreamPosition,
-    SubscribeToStreamOptions, SubscribeToAllOptions, SubscriptionFilter,
+    Client, EventData, ReadStreamOptions, StreamPosition, SubscribeToStreamOptions,
 };
 use serde::{Deserialize, Serialize};
 use std::env;
+use tokio_stream::StreamExt;
 use uuid::Uuid;
 
+use event_broker::Filter;
+
 #[derive(Debug, Serialize, Deserialize)]
-struct OrderCreated {
-    order_id: String,
-    customer_id: String,
-    amount: f64,
+pub struct OrderCreated {
+    pub order_id: String,
+    pub customer_id: String,
+    pub amount: f64,
 }
 
+mod batch_append;
+mod batch_append_client;
+mod broker;
+mod checkpoint;
+mod event_broker;
+mod graphql_bridge;
+mod graphql_gateway;
 mod projection;
+mod resilience;
+mod router;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,6 +35,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if args.len() > 1 && args[1] == "projection" {
         return projection::run_projection().await;
     }
+    if args.len() > 1 && args[1] == "batch" {
+        return batch_append_client::run_batch_demo().await;
+    }
+    if args.len() > 1 && args[1] == "graphql" {
+        return graphql_bridge::run_server().await;
+    }
+    if args.len() > 1 && args[1] == "gateway" {
+        return graphql_gateway::run_server().await;
+    }
+    if args.len() > 1 && args[1] == "router" {
+        return router::run_demo().await;
+    }
 
     let connection_string = env::var("KURRENTDB_CONNECTION_STRING")
         .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
@@ -86,116 +109,45 @@ Starting catch-up subscription to stream...");
 
     println!("Subscription stopped");
 
-    // === CATCH-UP SUBSCRIPTION ($all) ===
-    println!("\nStarting catch-up subscription to $all (reading 3 non-system events)...");
-
-    let options = SubscribeToAllOptions::default();
-    let mut all_subscription = client.subscribe_to_all(&options).await;
-
-    let mut count = 0;
-    loop {
-        match all_subscription.next().await {
-            Ok(event) => {
-                let original = event.get_original_event();
-                if !original.stream_id().starts_with('$') {
-                    println!(
-                        "  [Sub $all] Stream: {}, Type: {}",
-                        original.stream_id(), original.event_type
-                    );
-                    count += 1;
-                    if count >= 3 {
-                        break;
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Subscription error: {}", e);
-                break;
-            }
+    // === FAN-OUT BROKER ($all, one connection, many client-side filters) ===
+    // Instead of opening one `subscribe_to_all` per filtered view, open a
+    // single upstream subscription and fan it out to several in-process
+    // consumers, each applying its own predicate client-side.
+    println!("\nStarting fan-out broker over a single $all subscription...");
+
+    let broker = event_broker::EventBroker::new(client.clone(), 1024);
+
+    // Subscriber 1: the unfiltered $all view (reading 3).
+    let mut unfiltered_sub = Box::pin(broker.subscribe(Filter::new()));
+    // Subscriber 2: all non-system events (reading 3).
+    let mut all_sub = Box::pin(broker.subscribe(Filter::new().exclude_system_events()));
+    // Subscriber 3: events on `order-*` streams (reading 2).
+    let mut order_sub = Box::pin(broker.subscribe(Filter::new().stream_prefix("order-")));
+    // Subscriber 4: `Order*` event types (reading 2).
+    let mut type_sub = Box::pin(broker.subscribe(Filter::new().event_type_prefix("Order")));
+
+    for _ in 0..3 {
+        if let Some(event) = unfiltered_sub.next().await {
+            let original = event.get_original_event();
+            println!("  [Sub $all raw] Stream: {}, Type: {}", original.stream_id(), original.event_type);
         }
     }
-
-    // === FILTERED SUBSCRIPTION (exclude system events) ===
-    println!("\nStarting filtered subscription (excluding system events)...");
-
-    let filter = SubscriptionFilter::on_event_type().exclude_system_events();
-    let options = SubscribeToAllOptions::default().filter(filter);
-    let mut filtered_sub = client.subscribe_to_all(&options).await;
-
-    let mut count = 0;
-    loop {
-        match filtered_sub.next().await {
-            Ok(event) => {
-                let original = event.get_original_event();
-                println!(
-                    "  [Filtered] Stream: {}, Type: {}",
-                    original.stream_id(), original.event_type
-                );
-                count += 1;
-                if count >= 3 {
-                    break;
-                }
-            }
-            Err(e) => {
-                println!("Filtered subscription error: {}", e);
-                break;
-            }
+    for _ in 0..3 {
+        if let Some(event) = all_sub.next().await {
+            let original = event.get_original_event();
+            println!("  [Sub $all] Stream: {}, Type: {}", original.stream_id(), original.event_type);
         }
     }
-
-    // === FILTERED SUBSCRIPTION (by stream prefix) ===
-    println!("\nStarting filtered subscription (stream prefix 'order-')...");
-
-    let filter = SubscriptionFilter::on_stream_name().add_prefix("order-");
-    let options = SubscribeToAllOptions::default().filter(filter);
-    let mut prefix_sub = client.subscribe_to_all(&options).await;
-
-    let mut count = 0;
-    loop {
-        match prefix_sub.next().await {
-            Ok(event) => {
-                let original = event.get_original_event();
-                println!(
-                    "  [Prefix Filter] Stream: {}, Type: {}",
-                    original.stream_id(), original.event_type
-                );
-                count += 1;
-                if count >= 2 {
-                    break;
-                }
-            }
-            Err(e) => {
-                println!("Prefix filtered subscription error: {}", e);
-                break;
-            }
+    for _ in 0..2 {
+        if let Some(event) = order_sub.next().await {
+            let original = event.get_original_event();
+            println!("  [Prefix Filter] Stream: {}, Type: {}", original.stream_id(), original.event_type);
         }
     }
-
-    // === FILTERED SUBSCRIPTION (by event type prefix) ===
-    println!("\nStarting filtered subscription (event type prefix 'Order')...");
-
-    let filter = SubscriptionFilter::on_event_type().add_prefix("Order");
-    let options = SubscribeToAllOptions::default().filter(filter);
-    let mut event_type_sub = client.subscribe_to_all(&options).await;
-
-    let mut count = 0;
-    loop {
-        match event_type_sub.next().await {
-            Ok(event) => {
-                let original = event.get_original_event();
-                println!(
-                    "  [Event Type Filter] Stream: {}, Type: {}",
-                    original.stream_id(), original.event_type
-                );
-                count += 1;
-                if count >= 2 {
-                    break;
-                }
-            }
-            Err(e) => {
-                println!("Event type filtered subscription error: {}", e);
-                break;
-            }
+    for _ in 0..2 {
+        if let Some(event) = type_sub.next().await {
+            let original = event.get_original_event();
+            println!("  [Event Type Filter] Stream: {}, Type: {}", original.stream_id(), original.event_type);
         }
     }
 
@@ -0,0 +1,104 @@
+//! Administration surface for persistent subscriptions.
+//!
+//! The consume side (create / connect / ack / nack) is demonstrated in
+//! `persistent_subscription.rs`. This module wraps the *management* calls the
+//! gRPC client exposes so an operator can inspect a group's live buffer and
+//! parked counts, retune its settings after creation, and — most usefully —
+//! replay messages that were `NakAction::Park`ed once the underlying bug is
+//! fixed.
+
+use kurrentdb::{
+    Client, DeletePersistentSubscriptionOptions, ListPersistentSubscriptionsOptions,
+    PersistentSubscriptionInfo, PersistentSubscriptionSettings, PersistentSubscriptionToStreamOptions,
+    ReplayParkedMessagesOptions,
+};
+
+/// A thin typed facade over the client's persistent-subscription management
+/// endpoints, scoped to a single connection.
+pub struct SubscriptionAdmin {
+    client: Client,
+}
+
+impl SubscriptionAdmin {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// List every persistent subscription on the server.
+    pub async fn list(
+        &self,
+    ) -> Result<Vec<PersistentSubscriptionInfo>, Box<dyn std::error::Error>> {
+        let options = ListPersistentSubscriptionsOptions::default();
+        Ok(self.client.list_all_persistent_subscriptions(&options).await?)
+    }
+
+    /// List the persistent subscriptions bound to a single stream.
+    pub async fn list_for_stream(
+        &self,
+        stream: &str,
+    ) -> Result<Vec<PersistentSubscriptionInfo>, Box<dyn std::error::Error>> {
+        let options = ListPersistentSubscriptionsOptions::default();
+        Ok(self
+            .client
+            .list_persistent_subscriptions_for_stream(stream, &options)
+            .await?)
+    }
+
+    /// Fetch live stats (buffer/parked counts, connections) for one group.
+    pub async fn info(
+        &self,
+        stream: &str,
+        group: &str,
+    ) -> Result<PersistentSubscriptionInfo, Box<dyn std::error::Error>> {
+        Ok(self
+            .client
+            .get_persistent_subscription_info(stream, group, &Default::default())
+            .await?)
+    }
+
+    /// Retune a group's settings (max retry count, checkpoint interval, message
+    /// timeout, live buffer size, …) without recreating it.
+    pub async fn update(
+        &self,
+        stream: &str,
+        group: &str,
+        settings: PersistentSubscriptionSettings,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let options = PersistentSubscriptionToStreamOptions::default().settings(settings);
+        self.client
+            .update_persistent_subscription(stream, group, &options)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a group.
+    pub async fn delete(
+        &self,
+        stream: &str,
+        group: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let options = DeletePersistentSubscriptionOptions::default();
+        self.client
+            .delete_persistent_subscription(stream, group, &options)
+            .await?;
+        Ok(())
+    }
+
+    /// Re-deliver messages that were parked via `NakAction::Park`. Pass
+    /// `Some(n)` to stop after `n` messages, or `None` to replay everything.
+    pub async fn replay_parked(
+        &self,
+        stream: &str,
+        group: &str,
+        stop_at: Option<i32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut options = ReplayParkedMessagesOptions::default();
+        if let Some(n) = stop_at {
+            options = options.stop_at(n as usize);
+        }
+        self.client
+            .replay_parked_messages(stream, group, &options)
+            .await?;
+        Ok(())
+    }
+}
@@ -4,6 +4,11 @@ use kurrentdb::{Client, EventData, NakAction, PersistentSubscriptionOptions};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+mod persistent_admin;
+mod persistent_worker;
+use persistent_admin::SubscriptionAdmin;
+use persistent_worker::{CompetingConsumer, Decision};
+
 #[derive(Debug, Serialize, Deserialize)]
 struct OrderCreated {
     order_id: String,
@@ -119,6 +124,59 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // === ADMINISTRATION: inspect parked count, then replay ===
+    // Events that took a `NakAction::Park` branch above (an amount in
+    // (20, 25], or a failed ack) sit in the parked queue. The test amounts
+    // 10, 20 and 30 are acked, skipped and retried, so the count is usually 0.
+    // Once the processing bug is fixed, an operator inspects the group and
+    // replays whatever is parked.
+    let admin = SubscriptionAdmin::new(client.clone());
+
+    let info = admin.info(stream_name, group_name).await?;
+    println!(
+        "\nGroup '{}' on '{}': {} parked message(s), {} connection(s)",
+        group_name,
+        stream_name,
+        info.stats.parked_message_count,
+        info.connections.len()
+    );
+
+    println!("Replaying parked messages...");
+    admin.replay_parked(stream_name, group_name, None).await?;
+    println!("Requested replay of parked messages");
+
+    // === COMPETING CONSUMERS (order-* prefix) ===
+    // Load-balance order events across a group of workers. Run this binary in
+    // several processes and KurrentDB divides the events between them.
+    println!("\n=== Competing consumer over order-* ===");
+
+    let worker = CompetingConsumer::new(client.clone(), "order-workers");
+    worker.create(Default::default()).await?;
+
+    // Seed a couple of order-* events for the group to deliver.
+    for i in 0..2 {
+        let order = OrderCreated {
+            order_id: Uuid::new_v4().to_string(),
+            amount: 5.0 * (i + 1) as f64,
+        };
+        let event = EventData::json("OrderCreated", &order)?.id(Uuid::new_v4());
+        client
+            .append_to_stream(format!("order-{}", order.order_id), &Default::default(), event)
+            .await?;
+    }
+
+    worker
+        .run(2, |event| {
+            let original = event.get_original_event();
+            println!("  [worker] {} on {}", original.event_type, original.stream_id());
+            match original.as_json::<OrderCreated>() {
+                Ok(_) => Decision::Ack,
+                // Unparseable payloads get parked for later inspection.
+                Err(_) => Decision::Park,
+            }
+        })
+        .await?;
+
     println!("\nDone!");
     Ok(())
 }
@@ -0,0 +1,120 @@
+//! Ergonomic wrapper for competing-consumer (persistent) subscriptions.
+//!
+//! Catch-up subscriptions replay the whole stream in every process; persistent
+//! subscriptions let KurrentDB load-balance events across many worker processes
+//! in a named group. This wraps the group lifecycle (create / update / delete)
+//! over an `order-*` prefix plus a consume loop that hands each event to a
+//! handler and translates the returned [`Decision`] into ack/nack.
+
+use kurrentdb::{
+    Client, NakAction, PersistentSubscriptionSettings, PersistentSubscriptionToAllOptions,
+    ResolvedEvent, SubscriptionFilter,
+};
+
+/// What the handler wants done with an event.
+#[derive(Debug, Clone, Copy)]
+pub enum Decision {
+    /// Processed successfully.
+    Ack,
+    /// Transient failure; redeliver immediately.
+    Retry,
+    /// Permanent failure; move to the parked queue for inspection.
+    Park,
+    /// Ignore this event and move on.
+    Skip,
+}
+
+/// A competing consumer bound to a persistent-subscription group over the
+/// `order-*` streams.
+pub struct CompetingConsumer {
+    client: Client,
+    group: String,
+}
+
+impl CompetingConsumer {
+    pub fn new(client: Client, group: &str) -> Self {
+        Self {
+            client,
+            group: group.to_string(),
+        }
+    }
+
+    fn options(settings: PersistentSubscriptionSettings) -> PersistentSubscriptionToAllOptions {
+        let filter = SubscriptionFilter::on_stream_name().add_prefix("order-");
+        PersistentSubscriptionToAllOptions::default()
+            .settings(settings)
+            .filter(filter)
+    }
+
+    /// Create the group over `order-*`. Safe to call when it already exists —
+    /// the error is reported, not propagated.
+    pub async fn create(
+        &self,
+        settings: PersistentSubscriptionSettings,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let options = Self::options(settings);
+        match self
+            .client
+            .create_persistent_subscription_to_all(&self.group, &options)
+            .await
+        {
+            Ok(_) => println!("Created group '{}' over order-*", self.group),
+            Err(e) => println!("Group '{}' may already exist: {}", self.group, e),
+        }
+        Ok(())
+    }
+
+    /// Retune the group's settings in place.
+    pub async fn update(
+        &self,
+        settings: PersistentSubscriptionSettings,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let options = Self::options(settings);
+        self.client
+            .update_persistent_subscription_to_all(&self.group, &options)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete the group.
+    pub async fn delete(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.client
+            .delete_persistent_subscription_to_all(&self.group, &Default::default())
+            .await?;
+        Ok(())
+    }
+
+    /// Connect as a member of the group and process up to `max_events` events,
+    /// dispatching each to `handler` and acting on its [`Decision`]. Stopping
+    /// after `max_events` keeps the example bounded; a real worker loops
+    /// forever.
+    pub async fn run<F>(
+        &self,
+        max_events: usize,
+        handler: F,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        F: Fn(&ResolvedEvent) -> Decision,
+    {
+        let mut subscription = self
+            .client
+            .subscribe_to_persistent_subscription_to_all(&self.group, &Default::default())
+            .await?;
+
+        let mut processed = 0;
+        while processed < max_events {
+            let event = subscription.next().await?;
+
+            match handler(&event) {
+                Decision::Ack => subscription.ack(&event).await?,
+                Decision::Retry => subscription.nack(&event, NakAction::Retry).await?,
+                Decision::Park => subscription.nack(&event, NakAction::Park).await?,
+                Decision::Skip => subscription.nack(&event, NakAction::Skip).await?,
+            }
+
+            processed += 1;
+        }
+
+        Ok(())
+    }
+}
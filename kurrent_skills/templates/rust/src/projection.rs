@@ -2,7 +2,8 @@
 //! Demonstrates: Subscribe to events, build state, track checkpoint
 
 use kurrentdb::{
-    Client, EventData, Position, SubscribeToAllOptions, SubscriptionFilter,
+    Client, EventData, Position, ReadStreamOptions, StreamPosition, SubscribeToAllOptions,
+    SubscriptionFilter,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -10,15 +11,110 @@ use std::collections::HashMap;
 use std::env;
 use uuid::Uuid;
 
+use crate::batch_append::{BatchAppender, BatchConfig};
+use crate::broker::Broker;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
 // === MINIMAL PROJECTION FRAMEWORK ===
 
 type EventHandler = Box<dyn Fn(&Value, &Value) -> Value + Send + Sync>;
 
+/// Persisted form of a `$all` [`Position`]. `Position` itself is not
+/// `Serialize`, so we round-trip through its `commit`/`prepare` offsets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Checkpoint {
+    commit: u64,
+    prepare: u64,
+}
+
+impl From<Position> for Checkpoint {
+    fn from(p: Position) -> Self {
+        Self { commit: p.commit, prepare: p.prepare }
+    }
+}
+
+impl From<Checkpoint> for Position {
+    fn from(c: Checkpoint) -> Self {
+        Position { commit: c.commit, prepare: c.prepare }
+    }
+}
+
+/// Durable storage for a projection's last committed `$all` position.
+///
+/// The in-memory state can always be rebuilt by replaying `$all`, so the only
+/// thing worth persisting is *how far we got*. A projection calls
+/// [`CheckpointStore::load`] on startup to resume and
+/// [`CheckpointStore::save`] periodically as it makes progress.
+#[allow(async_fn_in_trait)]
+pub trait CheckpointStore {
+    async fn load(&self) -> Result<Option<Position>, Box<dyn std::error::Error>>;
+    async fn save(&self, position: Position) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// A [`CheckpointStore`] that records checkpoints as events on a dedicated
+/// KurrentDB stream (`projections-<name>-checkpoint`). The latest event on
+/// that stream is the committed position, recovered with a single backwards
+/// read.
+///
+/// The checkpoint lives on a regular (non-`$`) stream with a non-`$` event
+/// type so it can be written with ordinary credentials. Writing to a true
+/// `$`-prefixed system stream/event type is admin-only by default and would
+/// fail with `AccessDenied` for the unauthenticated connection the examples
+/// use.
+pub struct StreamCheckpointStore {
+    client: Client,
+    stream: String,
+}
+
+impl StreamCheckpointStore {
+    pub fn new(client: Client, projection_name: &str) -> Self {
+        Self {
+            client,
+            stream: format!("projections-{}-checkpoint", projection_name),
+        }
+    }
+}
+
+impl CheckpointStore for StreamCheckpointStore {
+    async fn load(&self) -> Result<Option<Position>, Box<dyn std::error::Error>> {
+        let options = ReadStreamOptions::default()
+            .position(StreamPosition::End)
+            .backwards()
+            .max_count(1);
+
+        let mut stream = match self.client.read_stream(self.stream.clone(), &options).await {
+            Ok(stream) => stream,
+            // A projection that has never checkpointed has no stream yet.
+            Err(kurrentdb::Error::ResourceNotFound) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        match stream.next().await {
+            Ok(Some(event)) => {
+                let checkpoint: Checkpoint = event.get_original_event().as_json()?;
+                Ok(Some(checkpoint.into()))
+            }
+            // The missing-stream case can also surface on the first read.
+            Ok(None) | Err(kurrentdb::Error::ResourceNotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save(&self, position: Position) -> Result<(), Box<dyn std::error::Error>> {
+        let event = EventData::json("checkpoint", &Checkpoint::from(position))?.id(Uuid::new_v4());
+        self.client
+            .append_to_stream(self.stream.clone(), &Default::default(), event)
+            .await?;
+        Ok(())
+    }
+}
+
 pub struct Projection {
     pub name: String,
     pub state: HashMap<String, Value>,
     pub checkpoint: Option<Position>,
     handlers: HashMap<String, EventHandler>,
+    updates: Broker<(String, Value)>,
 }
 
 impl Projection {
@@ -28,9 +124,19 @@ impl Projection {
             state: HashMap::new(),
             checkpoint: None,
             handlers: HashMap::new(),
+            updates: Broker::new(),
         }
     }
 
+    /// Subscribe to post-`apply` state changes. The returned stream yields
+    /// `(stream_id, new_state)` every time an event mutates a stream's state,
+    /// so many downstream tasks (cache warmer, websocket pusher, alerting rule)
+    /// can react to the same projection without each opening its own KurrentDB
+    /// subscription.
+    pub fn subscribe(&self) -> UnboundedReceiverStream<(String, Value)> {
+        self.updates.subscribe()
+    }
+
     pub fn on<F>(mut self, event_type: &str, handler: F) -> Self
     where
         F: Fn(&Value, &Value) -> Value + Send + Sync + 'static,
@@ -43,12 +149,55 @@ impl Projection {
         self.state.get(stream_id)
     }
 
+    /// Reload the last committed position from `store` so the projection can
+    /// continue where it left off instead of replaying `$all` from the start.
+    ///
+    /// Returns the `StreamPosition` to hand to [`SubscribeToAllOptions`]: the
+    /// stored position when one exists, otherwise `StreamPosition::Start`.
+    pub async fn resume_from<S: CheckpointStore>(
+        &mut self,
+        store: &S,
+    ) -> Result<StreamPosition<Position>, Box<dyn std::error::Error>> {
+        match store.load().await? {
+            Some(position) => {
+                self.checkpoint = Some(position);
+                Ok(StreamPosition::Position(position))
+            }
+            None => Ok(StreamPosition::Start),
+        }
+    }
+
+    /// Commit the current checkpoint to `store`. No-op while the projection has
+    /// no checkpoint, i.e. it has neither applied an event nor resumed from a
+    /// stored position.
+    pub async fn persist_checkpoint<S: CheckpointStore>(
+        &self,
+        store: &S,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(position) = self.checkpoint {
+            store.save(position).await?;
+        }
+        Ok(())
+    }
+
     pub fn apply(&mut self, stream_id: &str, event_type: &str, data: &[u8], position: Position) -> bool {
+        // Resuming from a stored position is exclusive, so the boundary event is
+        // not normally redelivered. This guard is defensive: it keeps `apply`
+        // idempotent under the at-least-once model (duplicate delivery on a
+        // reconnect, or replaying from a stale checkpoint) by skipping anything
+        // at or before the checkpoint.
+        if let Some(checkpoint) = self.checkpoint {
+            if position <= checkpoint {
+                return false;
+            }
+        }
+
         if let Some(handler) = self.handlers.get(event_type) {
             let current = self.state.get(stream_id).cloned().unwrap_or(json!({}));
             let event_data: Value = serde_json::from_slice(data).unwrap_or(json!({}));
             let new_state = handler(&current, &event_data);
-            self.state.insert(stream_id.to_string(), new_state);
+            self.state.insert(stream_id.to_string(), new_state.clone());
+            self.updates.publish((stream_id.to_string(), new_state));
             self.checkpoint = Some(position);
             return true;
         }
@@ -78,18 +227,11 @@ struct OrderShipped {
     shipped_at: String,
 }
 
-pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
-    // === SETUP ===
-    let connection_string = env::var("KURRENTDB_CONNECTION_STRING")
-        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
-
-    let settings = connection_string.parse()?;
-    let client = Client::new(settings)?;
-
-    println!("Connected to KurrentDB at {}", connection_string);
-
-    // === DEFINE PROJECTION ===
-    let mut order_projection = Projection::new("OrderSummary")
+/// Build the example order-summary projection. Shared by the console demo
+/// ([`run_projection`]) and the GraphQL gateway so the handler logic lives in
+/// one place.
+pub fn order_summary() -> Projection {
+    Projection::new("OrderSummary")
         .on("OrderCreated", |_state, data| {
             json!({
                 "orderId": data.get("orderId"),
@@ -124,7 +266,21 @@ pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
             let mut new_state = state.clone();
             new_state["status"] = json!("completed");
             new_state
-        });
+        })
+}
+
+pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
+    // === SETUP ===
+    let connection_string = env::var("KURRENTDB_CONNECTION_STRING")
+        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
+
+    let settings = connection_string.parse()?;
+    let client = Client::new(settings)?;
+
+    println!("Connected to KurrentDB at {}", connection_string);
+
+    // === DEFINE PROJECTION ===
+    let mut order_projection = order_summary();
 
     // === TEST: Append test events ===
     println!("\n=== Appending test events ===");
@@ -134,51 +290,64 @@ pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
     let stream_1 = format!("order-{}", order_id_1);
     let stream_2 = format!("order-{}", order_id_2);
 
-    // Order 1: Created -> ItemAdded -> Shipped -> Completed
-    let event = EventData::json("OrderCreated", &OrderCreated {
-        order_id: order_id_1.clone(),
-        customer_id: "cust-1".to_string(),
-        amount: 100.0,
-    })?.id(Uuid::new_v4());
-    client.append_to_stream(stream_1.clone(), &Default::default(), event).await?;
-
-    let event = EventData::json("ItemAdded", &ItemAdded {
-        item: "Widget".to_string(),
-        price: 25.0,
-    })?.id(Uuid::new_v4());
-    client.append_to_stream(stream_1.clone(), &Default::default(), event).await?;
-
-    let event = EventData::json("OrderShipped", &OrderShipped {
-        shipped_at: "2024-01-15T10:00:00Z".to_string(),
-    })?.id(Uuid::new_v4());
-    client.append_to_stream(stream_1.clone(), &Default::default(), event).await?;
-
-    let event = EventData::json("OrderCompleted", &json!({}))?.id(Uuid::new_v4());
-    client.append_to_stream(stream_1.clone(), &Default::default(), event).await?;
-
-    // Order 2: Created -> ItemAdded (still pending)
-    let event = EventData::json("OrderCreated", &OrderCreated {
-        order_id: order_id_2.clone(),
-        customer_id: "cust-2".to_string(),
-        amount: 50.0,
-    })?.id(Uuid::new_v4());
-    client.append_to_stream(stream_2.clone(), &Default::default(), event).await?;
-
-    let event = EventData::json("ItemAdded", &ItemAdded {
-        item: "Gadget".to_string(),
-        price: 30.0,
-    })?.id(Uuid::new_v4());
-    client.append_to_stream(stream_2.clone(), &Default::default(), event).await?;
+    // Stream events through the batch appender: submit every event first, then
+    // await the results together so each stream's events coalesce into one
+    // flush rather than round-tripping individually.
+    let appender = BatchAppender::new(client.clone(), BatchConfig::default());
+
+    let submissions = vec![
+        // Order 1: Created -> ItemAdded -> Shipped -> Completed
+        appender.append(stream_1.clone(), EventData::json("OrderCreated", &OrderCreated {
+            order_id: order_id_1.clone(),
+            customer_id: "cust-1".to_string(),
+            amount: 100.0,
+        })?.id(Uuid::new_v4())),
+        appender.append(stream_1.clone(), EventData::json("ItemAdded", &ItemAdded {
+            item: "Widget".to_string(),
+            price: 25.0,
+        })?.id(Uuid::new_v4())),
+        appender.append(stream_1.clone(), EventData::json("OrderShipped", &OrderShipped {
+            shipped_at: "2024-01-15T10:00:00Z".to_string(),
+        })?.id(Uuid::new_v4())),
+        appender.append(stream_1.clone(), EventData::json("OrderCompleted", &json!({}))?.id(Uuid::new_v4())),
+        // Order 2: Created -> ItemAdded (still pending)
+        appender.append(stream_2.clone(), EventData::json("OrderCreated", &OrderCreated {
+            order_id: order_id_2.clone(),
+            customer_id: "cust-2".to_string(),
+            amount: 50.0,
+        })?.id(Uuid::new_v4())),
+        appender.append(stream_2.clone(), EventData::json("ItemAdded", &ItemAdded {
+            item: "Gadget".to_string(),
+            price: 30.0,
+        })?.id(Uuid::new_v4())),
+    ];
+    futures::future::try_join_all(submissions).await?;
 
     println!("Created order streams: {}, {}", stream_1, stream_2);
 
     // === RUN PROJECTION ===
     println!("\n=== Running projection ===");
 
+    // Recover the last committed position (if any) so a restart resumes rather
+    // than replaying $all from the beginning.
+    let checkpoint_store = StreamCheckpointStore::new(client.clone(), &order_projection.name);
+    let start_from = order_projection.resume_from(&checkpoint_store).await?;
+    match start_from {
+        StreamPosition::Start => println!("No checkpoint found, starting from $all start"),
+        _ => println!("Resuming from checkpoint {:?}", order_projection.checkpoint),
+    }
+
+    // Persist a checkpoint every N applied events (trade durability against
+    // write amplification).
+    const CHECKPOINT_EVERY: u32 = 2;
+
     let filter = SubscriptionFilter::on_event_type().exclude_system_events();
-    let options = SubscribeToAllOptions::default().filter(filter);
+    let options = SubscribeToAllOptions::default()
+        .position(start_from)
+        .filter(filter);
     let mut subscription = client.subscribe_to_all(&options).await;
 
+    let mut since_checkpoint = 0u32;
     let mut processed_count = 0;
     let mut target_events_count: HashMap<String, i32> = HashMap::new();
     target_events_count.insert(stream_1.clone(), 0);
@@ -201,6 +370,12 @@ pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
                     if let Some(count) = target_events_count.get_mut(stream_id) {
                         *count += 1;
                     }
+
+                    since_checkpoint += 1;
+                    if since_checkpoint >= CHECKPOINT_EVERY {
+                        order_projection.persist_checkpoint(&checkpoint_store).await?;
+                        since_checkpoint = 0;
+                    }
                 }
 
                 // Stop when we've processed all test events for both streams
@@ -222,6 +397,9 @@ pub async fn run_projection() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Flush the final position so the next run resumes past the last event.
+    order_projection.persist_checkpoint(&checkpoint_store).await?;
+
     // === VERIFY RESULTS ===
     println!("\n=== Projection Results ===");
 
@@ -0,0 +1,154 @@
+//! Reconnecting catch-up subscriptions.
+//!
+//! The raw subscription examples `break` out of their loop on the first error
+//! `next()` returns, so a transient network blip kills the consumer. The
+//! helpers here wrap `subscribe_to_all` / `subscribe_to_stream` in a retry loop
+//! driven by a [`Retry`] policy and exponential backoff with jitter. They
+//! remember the last successfully processed `Position` / revision and resume
+//! from it on reconnect, resetting the backoff after each delivered event.
+
+use std::time::Duration;
+
+use async_stream::stream;
+use kurrentdb::{
+    Client, Position, ResolvedEvent, StreamPosition, SubscribeToAllOptions,
+    SubscribeToStreamOptions, SubscriptionFilter,
+};
+use rand::Rng;
+use tokio_stream::Stream;
+
+/// How many times to re-establish a dropped subscription, mirroring the
+/// official client's reconnection enum.
+#[derive(Debug, Clone, Copy)]
+pub enum Retry {
+    /// Reconnect forever.
+    Indefinitely,
+    /// Reconnect at most `n` times before giving up.
+    Only(usize),
+}
+
+/// Exponential backoff with full jitter, capped at `max`.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub factor: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            factor: 2,
+        }
+    }
+}
+
+impl Backoff {
+    /// Sleep for the delay of `attempt` (0-based): an exponentially growing
+    /// ceiling capped at `max`, with full jitter applied.
+    async fn sleep(&self, attempt: u32) {
+        let base = self
+            .initial
+            .saturating_mul(self.factor.saturating_pow(attempt))
+            .min(self.max);
+        let jittered = rand::thread_rng().gen_range(0..=base.as_millis() as u64);
+        tokio::time::sleep(Duration::from_millis(jittered)).await;
+    }
+}
+
+/// Returns true while the [`Retry`] budget allows another attempt.
+fn may_retry(policy: Retry, attempts: u32) -> bool {
+    match policy {
+        Retry::Indefinitely => true,
+        Retry::Only(n) => (attempts as usize) < n,
+    }
+}
+
+/// Resiliently subscribe to `$all`, yielding every [`ResolvedEvent`] exactly as
+/// a plain `subscribe_to_all` would, but transparently reconnecting from the
+/// last delivered position on recoverable errors.
+pub fn resilient_subscribe_to_all(
+    client: Client,
+    filter: Option<SubscriptionFilter>,
+    start: StreamPosition<Position>,
+    retry: Retry,
+    backoff: Backoff,
+) -> impl Stream<Item = ResolvedEvent> {
+    stream! {
+        let mut position = start;
+        let mut attempts: u32 = 0;
+
+        loop {
+            let mut options = SubscribeToAllOptions::default().position(position);
+            if let Some(filter) = filter.clone() {
+                options = options.filter(filter);
+            }
+            let mut subscription = client.subscribe_to_all(&options).await;
+
+            loop {
+                match subscription.next().await {
+                    Ok(event) => {
+                        // Remember where to resume, then hand the event out.
+                        position = StreamPosition::Position(event.get_original_event().position);
+                        attempts = 0;
+                        yield event;
+                    }
+                    Err(e) => {
+                        eprintln!("$all subscription dropped: {e}");
+                        break;
+                    }
+                }
+            }
+
+            if !may_retry(retry, attempts) {
+                eprintln!("giving up after {attempts} reconnection attempt(s)");
+                break;
+            }
+            backoff.sleep(attempts).await;
+            attempts += 1;
+        }
+    }
+}
+
+/// Resiliently subscribe to a single stream, resuming from the last delivered
+/// revision on reconnect.
+pub fn resilient_subscribe_to_stream(
+    client: Client,
+    stream_name: String,
+    start: StreamPosition<u64>,
+    retry: Retry,
+    backoff: Backoff,
+) -> impl Stream<Item = ResolvedEvent> {
+    stream! {
+        let mut revision = start;
+        let mut attempts: u32 = 0;
+
+        loop {
+            let options = SubscribeToStreamOptions::default().start_from(revision);
+            let mut subscription = client.subscribe_to_stream(stream_name.clone(), &options).await;
+
+            loop {
+                match subscription.next().await {
+                    Ok(event) => {
+                        revision = StreamPosition::Position(event.get_original_event().revision);
+                        attempts = 0;
+                        yield event;
+                    }
+                    Err(e) => {
+                        eprintln!("stream '{stream_name}' subscription dropped: {e}");
+                        break;
+                    }
+                }
+            }
+
+            if !may_retry(retry, attempts) {
+                eprintln!("giving up after {attempts} reconnection attempt(s)");
+                break;
+            }
+            backoff.sleep(attempts).await;
+            attempts += 1;
+        }
+    }
+}
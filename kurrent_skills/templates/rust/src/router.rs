@@ -0,0 +1,116 @@
+//! Typed event-type routing.
+//!
+//! The read and subscription loops hand-call `original.as_json::<OrderCreated>()`,
+//! which only works because the example has a single event type. [`Router`] lets
+//! you register a handler per `event_type`, each with its own `Deserialize`
+//! target, and dispatches a `ResolvedEvent` to the matching typed handler. An
+//! optional fallback handles unknown types, and a deserialization failure on
+//! schema drift is reported rather than panicking.
+
+use std::collections::HashMap;
+
+use kurrentdb::{Client, ResolvedEvent, SubscribeToAllOptions, SubscriptionFilter};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::OrderCreated;
+
+type Handler = Box<dyn Fn(&ResolvedEvent) + Send + Sync>;
+
+/// Dispatches events to typed handlers keyed by event type.
+#[derive(Default)]
+pub struct Router {
+    handlers: HashMap<String, Handler>,
+    fallback: Option<Handler>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for `event_type`, deserializing the event body into
+    /// `T` first. If the body can't be deserialized into `T` (schema drift),
+    /// the error is logged and the handler is skipped.
+    pub fn on<T, F>(mut self, event_type: &str, handler: F) -> Self
+    where
+        T: DeserializeOwned,
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        let event_type = event_type.to_string();
+        let label = event_type.clone();
+        self.handlers.insert(
+            event_type,
+            Box::new(move |event| match event.get_original_event().as_json::<T>() {
+                Ok(value) => handler(value),
+                Err(e) => eprintln!("failed to deserialize '{label}': {e}"),
+            }),
+        );
+        self
+    }
+
+    /// Register a handler for events whose type has no registered handler.
+    pub fn fallback<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ResolvedEvent) + Send + Sync + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    /// Route one event to its handler, or the fallback, or drop it.
+    pub fn dispatch(&self, event: &ResolvedEvent) {
+        let event_type = &event.get_original_event().event_type;
+        if let Some(handler) = self.handlers.get(event_type) {
+            handler(event);
+        } else if let Some(fallback) = &self.fallback {
+            fallback(event);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderShipped {
+    #[serde(rename = "shippedAt")]
+    shipped_at: String,
+}
+
+/// Drive a `$all` subscription through a [`Router`] with handlers for several
+/// event types, showing how heterogeneous streams are consumed without manual
+/// `match` + `as_json` boilerplate.
+pub async fn run_demo() -> Result<(), Box<dyn std::error::Error>> {
+    let connection_string = std::env::var("KURRENTDB_CONNECTION_STRING")
+        .unwrap_or_else(|_| "kurrentdb://localhost:2113?tls=false".to_string());
+    let client = Client::new(connection_string.parse()?)?;
+
+    let router = Router::new()
+        .on::<OrderCreated, _>("OrderCreated", |order| {
+            println!("  OrderCreated: {} (${:.2})", order.order_id, order.amount);
+        })
+        .on::<OrderShipped, _>("OrderShipped", |shipped| {
+            println!("  OrderShipped at {}", shipped.shipped_at);
+        })
+        .fallback(|event| {
+            println!("  unhandled: {}", event.get_original_event().event_type);
+        });
+
+    let filter = SubscriptionFilter::on_event_type().exclude_system_events();
+    let options = SubscribeToAllOptions::default().filter(filter);
+    let mut subscription = client.subscribe_to_all(&options).await;
+
+    let mut count = 0;
+    while count < 10 {
+        match subscription.next().await {
+            Ok(event) => {
+                router.dispatch(&event);
+                count += 1;
+            }
+            Err(e) => {
+                eprintln!("subscription error: {e}");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}